@@ -0,0 +1,126 @@
+use pyo3::prelude::*;
+use serde::Deserialize;
+
+/// A single US trade print from the EODHD websocket.
+#[pyclass]
+#[derive(Debug, Clone, Deserialize)]
+pub struct Trade {
+    #[pyo3(get)]
+    #[serde(rename = "s")]
+    pub symbol: String,
+    #[pyo3(get)]
+    #[serde(rename = "p")]
+    pub price: f64,
+    #[pyo3(get)]
+    #[serde(rename = "v")]
+    pub volume: u64,
+    #[pyo3(get)]
+    #[serde(rename = "t")]
+    pub timestamp: i64,
+    #[pyo3(get)]
+    #[serde(rename = "dp", default)]
+    pub dark_pool: bool,
+    #[pyo3(get)]
+    #[serde(rename = "c", default)]
+    pub conditions: Vec<u32>,
+}
+
+/// A top-of-book quote update from the EODHD websocket.
+#[pyclass]
+#[derive(Debug, Clone, Deserialize)]
+pub struct Quote {
+    #[pyo3(get)]
+    #[serde(rename = "s")]
+    pub symbol: String,
+    #[pyo3(get)]
+    #[serde(rename = "bp")]
+    pub bid_price: f64,
+    #[pyo3(get)]
+    #[serde(rename = "bs")]
+    pub bid_size: u64,
+    #[pyo3(get)]
+    #[serde(rename = "ap")]
+    pub ask_price: f64,
+    #[pyo3(get)]
+    #[serde(rename = "as")]
+    pub ask_size: u64,
+    #[pyo3(get)]
+    #[serde(rename = "t")]
+    pub timestamp: i64,
+}
+
+/// Anything that isn't a trade or quote: heartbeats, subscribe/unsubscribe
+/// acks, and auth confirmations. Carried as the raw frame text so callers can
+/// inspect it without the crate having to model every EODHD status shape.
+#[pyclass]
+#[derive(Debug, Clone)]
+pub struct StreamStatus {
+    #[pyo3(get)]
+    pub raw: String,
+}
+
+/// A frame decoded off the wire, dispatched to its most specific shape.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum EodhdFrame {
+    Trade(Trade),
+    Quote(Quote),
+    // The payload only needs to exist to make this variant a valid catch-all
+    // during untagged deserialization; callers get the original frame text
+    // from `deliver`, not this re-parsed value.
+    Status(#[allow(dead_code)] serde_json::Value),
+}
+
+/// Parses one websocket text frame into its typed representation. Frames
+/// that don't match the trade or quote shape fall back to `EodhdFrame::Status`
+/// carrying the parsed JSON value; frames that aren't even valid JSON become
+/// `EodhdFrame::Status` carrying the raw text as a JSON string. Nothing is
+/// silently dropped.
+pub fn parse_frame(text: &str) -> EodhdFrame {
+    match serde_json::from_str::<EodhdFrame>(text) {
+        Ok(frame) => frame,
+        Err(_) => EodhdFrame::Status(serde_json::Value::String(text.to_string())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_trade_frame() {
+        let text = r#"{"s":"AAPL","p":190.12,"v":100,"t":1690000000}"#;
+        match parse_frame(text) {
+            EodhdFrame::Trade(trade) => {
+                assert_eq!(trade.symbol, "AAPL");
+                assert_eq!(trade.price, 190.12);
+                assert_eq!(trade.volume, 100);
+                assert!(!trade.dark_pool);
+            }
+            other => panic!("expected Trade, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn parses_quote_frame() {
+        let text = r#"{"s":"AAPL","bp":190.10,"bs":5,"ap":190.15,"as":3,"t":1690000000}"#;
+        match parse_frame(text) {
+            EodhdFrame::Quote(quote) => {
+                assert_eq!(quote.symbol, "AAPL");
+                assert_eq!(quote.bid_price, 190.10);
+            }
+            other => panic!("expected Quote, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn routes_status_json_without_dropping() {
+        let text = r#"{"status_code":1,"message":"authenticated"}"#;
+        assert!(matches!(parse_frame(text), EodhdFrame::Status(_)));
+    }
+
+    #[test]
+    fn routes_non_json_text_as_status() {
+        assert!(matches!(parse_frame("PING"), EodhdFrame::Status(_)));
+    }
+}
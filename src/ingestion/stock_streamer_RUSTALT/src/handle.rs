@@ -0,0 +1,58 @@
+use std::sync::Mutex;
+
+use pyo3::exceptions::PyRuntimeError;
+use pyo3::prelude::*;
+use tokio::sync::{mpsc, oneshot};
+
+/// A command sent from Python into the running stream task.
+pub enum Command {
+    AddSymbols(Vec<String>),
+    RemoveSymbols(Vec<String>),
+}
+
+/// Handle to a running stream task, returned by `start_stream`. Lets callers
+/// adjust the live subscription set without tearing down the socket, and
+/// shut the task down cleanly when it's no longer needed.
+#[pyclass]
+pub struct StreamHandle {
+    pub(crate) tx: mpsc::UnboundedSender<Command>,
+    pub(crate) shutdown: Mutex<Option<oneshot::Sender<()>>>,
+}
+
+impl StreamHandle {
+    pub(crate) fn new(tx: mpsc::UnboundedSender<Command>, shutdown: oneshot::Sender<()>) -> Self {
+        Self {
+            tx,
+            shutdown: Mutex::new(Some(shutdown)),
+        }
+    }
+}
+
+#[pymethods]
+impl StreamHandle {
+    /// Subscribes to additional symbols on the already-open socket.
+    pub fn add_symbols(&self, symbols: Vec<String>) -> PyResult<()> {
+        self.tx
+            .send(Command::AddSymbols(symbols))
+            .map_err(|_| PyRuntimeError::new_err("stream task has already stopped"))
+    }
+
+    /// Unsubscribes from symbols on the already-open socket.
+    pub fn remove_symbols(&self, symbols: Vec<String>) -> PyResult<()> {
+        self.tx
+            .send(Command::RemoveSymbols(symbols))
+            .map_err(|_| PyRuntimeError::new_err("stream task has already stopped"))
+    }
+
+    /// Signals the background task to unsubscribe, close the socket, and
+    /// terminate. Calling this more than once is a no-op after the first.
+    pub fn stop(&self) -> PyResult<()> {
+        if let Some(shutdown) = self.shutdown.lock().unwrap().take() {
+            // The task may have already exited on its own (e.g. after a
+            // fatal error); a failed send just means there's nothing left
+            // to signal.
+            let _ = shutdown.send(());
+        }
+        Ok(())
+    }
+}
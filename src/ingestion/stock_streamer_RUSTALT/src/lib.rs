@@ -1,43 +1,264 @@
+mod frame;
+mod handle;
+mod rest;
+
+use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
 use futures_util::{SinkExt, StreamExt};
+use tokio::sync::{mpsc, oneshot};
 use tokio_tungstenite::connect_async;
+use tokio_tungstenite::tungstenite::Message;
 use url::Url;
+use std::time::Duration;
+
+use frame::{parse_frame, EodhdFrame, StreamStatus};
+use handle::{Command, StreamHandle};
+
+/// Initial delay before the first reconnect attempt.
+const BACKOFF_BASE: Duration = Duration::from_secs(1);
+/// Upper bound on the reconnect delay; we never back off further than this.
+const BACKOFF_CAP: Duration = Duration::from_secs(60);
+
+/// Maps a user-facing asset-class selector to its EODHD websocket path.
+fn resolve_market_path(endpoint: &str) -> Result<&'static str, String> {
+    match endpoint {
+        "us" => Ok("us"),
+        "us-quote" => Ok("us-quote"),
+        "forex" => Ok("forex"),
+        "crypto" => Ok("crypto"),
+        other => Err(format!(
+            "unknown EODHD endpoint '{other}', expected one of: us, us-quote, forex, crypto"
+        )),
+    }
+}
+
+/// `resolve_market_path`, raising a `ValueError` on an unknown endpoint.
+fn market_path(endpoint: &str) -> PyResult<&'static str> {
+    resolve_market_path(endpoint).map_err(PyValueError::new_err)
+}
+
+/// Builds a subscribe/unsubscribe action frame for the given symbols.
+fn action_frame(action: &str, symbols: &[String]) -> String {
+    serde_json::json!({
+        "action": action,
+        "symbols": symbols.join(","),
+    })
+    .to_string()
+}
+
+/// Decodes `text` and invokes the Python callback with the typed frame,
+/// acquiring the GIL for the call. Exceptions raised by the callback are
+/// printed (as a traceback) rather than propagated, since a misbehaving
+/// callback should not kill the stream.
+fn deliver(on_message: &Py<PyAny>, text: &str) {
+    Python::with_gil(|py| {
+        let result = match parse_frame(text) {
+            EodhdFrame::Trade(trade) => on_message.call1(py, (trade,)),
+            EodhdFrame::Quote(quote) => on_message.call1(py, (quote,)),
+            EodhdFrame::Status(_) => {
+                // Forward the original bytes rather than re-serializing the
+                // parsed `Value`, which would double-encode non-JSON text
+                // (e.g. a bare "PING") into a quoted JSON string literal.
+                let status = StreamStatus { raw: text.to_string() };
+                on_message.call1(py, (status,))
+            }
+        };
+        if let Err(err) = result {
+            err.print(py);
+        }
+    });
+}
+
+/// Outcome of a single `run_session` call, distinguishing a clean shutdown
+/// (requested via `StreamHandle::stop`) from a socket that simply closed and
+/// should be reconnected.
+enum SessionOutcome {
+    Stopped,
+    Disconnected,
+}
+
+/// Connects to the EODHD websocket, subscribes to `symbols`, and consumes
+/// frames until the socket errs out or closes, or a shutdown is requested on
+/// `shutdown`. While connected, also applies `add_symbols`/`remove_symbols`
+/// commands arriving on `commands`, mutating `symbols` in place so a
+/// subsequent reconnect resubscribes to the current set rather than the
+/// original one. Returns `Ok(Disconnected)` only after at least one message
+/// has been delivered, so the caller can decide whether to reset its
+/// backoff delay.
+async fn run_session(
+    symbols: &mut Vec<String>,
+    market: &str,
+    api_token: &str,
+    on_message: &Py<PyAny>,
+    commands: &mut mpsc::UnboundedReceiver<Command>,
+    shutdown: &mut oneshot::Receiver<()>,
+) -> Result<SessionOutcome, String> {
+    let ws_url = format!(
+        "wss://ws.eodhistoricaldata.com/ws/{market}?api_token={api_token}"
+    );
+    let url = Url::parse(&ws_url).map_err(|e| format!("invalid url: {e}"))?;
+    let mut ws_stream = tokio::select! {
+        res = connect_async(url) => res.map_err(|e| format!("connect failed: {e}"))?.0,
+        _ = &mut *shutdown => return Ok(SessionOutcome::Stopped),
+    };
+
+    ws_stream
+        .send(Message::Text(action_frame("subscribe", symbols)))
+        .await
+        .map_err(|e| format!("subscribe failed: {e}"))?;
+
+    let mut got_message = false;
+    loop {
+        tokio::select! {
+            msg = ws_stream.next() => {
+                match msg {
+                    Some(Ok(Message::Text(text))) => {
+                        got_message = true;
+                        deliver(on_message, &text);
+                    }
+                    Some(Ok(_)) => {}
+                    Some(Err(e)) => return Err(format!("read failed: {e}")),
+                    None => break,
+                }
+            }
+            cmd = commands.recv() => {
+                match cmd {
+                    Some(Command::AddSymbols(new_syms)) => {
+                        let frame = action_frame("subscribe", &new_syms);
+                        if ws_stream.send(Message::Text(frame)).await.is_err() {
+                            return Err("failed to send add_symbols frame".to_string());
+                        }
+                        for sym in new_syms {
+                            if !symbols.contains(&sym) {
+                                symbols.push(sym);
+                            }
+                        }
+                    }
+                    Some(Command::RemoveSymbols(old_syms)) => {
+                        let frame = action_frame("unsubscribe", &old_syms);
+                        if ws_stream.send(Message::Text(frame)).await.is_err() {
+                            return Err("failed to send remove_symbols frame".to_string());
+                        }
+                        symbols.retain(|s| !old_syms.contains(s));
+                    }
+                    None => {}
+                }
+            }
+            _ = &mut *shutdown => {
+                let frame = action_frame("unsubscribe", symbols);
+                let _ = ws_stream.send(Message::Text(frame)).await;
+                let _ = ws_stream.close(None).await;
+                return Ok(SessionOutcome::Stopped);
+            }
+        }
+    }
+
+    if got_message {
+        Ok(SessionOutcome::Disconnected)
+    } else {
+        Err("socket closed before any message was received".to_string())
+    }
+}
 
 #[pyfunction]
-pub fn start_stream(ticker: String, api_token: String) -> PyResult<()> {
+pub fn start_stream(
+    symbols: Vec<String>,
+    endpoint: String,
+    api_token: String,
+    on_message: PyObject,
+) -> PyResult<StreamHandle> {
+    let market = market_path(&endpoint)?;
+    let (tx, mut rx) = mpsc::unbounded_channel::<Command>();
+    let (shutdown_tx, mut shutdown_rx) = oneshot::channel::<()>();
+
     // Spawn a new thread with an async runtime
     std::thread::spawn(move || {
         let rt = tokio::runtime::Runtime::new().unwrap();
         rt.block_on(async move {
-            let ws_url = format!(
-                "wss://ws.eodhistoricaldata.com/ws/us?api_token={}",
-                api_token
-            );
-            let url = Url::parse(&ws_url).unwrap();
-            let (mut ws_stream, _) = connect_async(url).await.unwrap();
-
-            let subscribe_msg = format!(
-                "{{\"action\":\"subscribe\",\"symbols\":\"{}\"}}",
-                ticker
-            );
-            ws_stream.send(tokio_tungstenite::tungstenite::Message::Text(subscribe_msg)).await.unwrap();
-
-            while let Some(msg) = ws_stream.next().await {
-                match msg {
-                    Ok(tokio_tungstenite::tungstenite::Message::Text(text)) => {
-                        println!("Rust received: {}", text);
+            let mut symbols = symbols;
+            let mut delay = BACKOFF_BASE;
+            loop {
+                let outcome = run_session(
+                    &mut symbols,
+                    market,
+                    &api_token,
+                    &on_message,
+                    &mut rx,
+                    &mut shutdown_rx,
+                )
+                .await;
+                match outcome {
+                    Ok(SessionOutcome::Stopped) => break,
+                    Ok(SessionOutcome::Disconnected) => {
+                        delay = BACKOFF_BASE;
+                    }
+                    Err(reason) => {
+                        eprintln!(
+                            "stock_streamer: {:?} stream error ({reason}), retrying in {:.1}s",
+                            symbols,
+                            delay.as_secs_f64()
+                        );
+                        tokio::select! {
+                            _ = tokio::time::sleep(delay) => {}
+                            _ = &mut shutdown_rx => break,
+                        }
+                        delay = std::cmp::min(delay * 2, BACKOFF_CAP);
                     }
-                    _ => {}
                 }
             }
         });
     });
 
-    Ok(())
+    Ok(StreamHandle::new(tx, shutdown_tx))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn market_path_maps_known_endpoints() {
+        assert_eq!(resolve_market_path("us"), Ok("us"));
+        assert_eq!(resolve_market_path("us-quote"), Ok("us-quote"));
+        assert_eq!(resolve_market_path("forex"), Ok("forex"));
+        assert_eq!(resolve_market_path("crypto"), Ok("crypto"));
+    }
+
+    #[test]
+    fn market_path_rejects_unknown_endpoint() {
+        assert!(resolve_market_path("bogus").is_err());
+    }
+
+    #[test]
+    fn action_frame_builds_expected_json() {
+        let frame = action_frame("subscribe", &["AAPL".to_string(), "MSFT".to_string()]);
+        let parsed: serde_json::Value = serde_json::from_str(&frame).unwrap();
+        assert_eq!(parsed["action"], "subscribe");
+        assert_eq!(parsed["symbols"], "AAPL,MSFT");
+    }
+
+    #[test]
+    fn action_frame_escapes_symbols_with_quotes() {
+        let symbol = r#"AA"PL\"#.to_string();
+        let frame = action_frame("subscribe", std::slice::from_ref(&symbol));
+        let parsed: serde_json::Value = serde_json::from_str(&frame).unwrap();
+        assert_eq!(parsed["symbols"], symbol);
+    }
 }
 
 #[pymodule]
 fn stock_streamer(_py: Python, m: &PyModule) -> PyResult<()> {
+    m.add_class::<frame::Trade>()?;
+    m.add_class::<frame::Quote>()?;
+    m.add_class::<frame::StreamStatus>()?;
+    m.add_class::<StreamHandle>()?;
+    m.add_class::<rest::EodBar>()?;
+    m.add_class::<rest::IntradayBar>()?;
+    m.add_class::<rest::NewsItem>()?;
     m.add_function(wrap_pyfunction!(start_stream, m)?)?;
+    m.add_function(wrap_pyfunction!(rest::get_eod, m)?)?;
+    m.add_function(wrap_pyfunction!(rest::get_intraday, m)?)?;
+    m.add_function(wrap_pyfunction!(rest::get_news, m)?)?;
+    m.add_function(wrap_pyfunction!(rest::get_eod_batch, m)?)?;
     Ok(())
 }
@@ -0,0 +1,308 @@
+use futures_util::stream::{self, StreamExt};
+use pyo3::exceptions::{PyRuntimeError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use reqwest::header::CONTENT_TYPE;
+use serde::Deserialize;
+
+const BASE_URL: &str = "https://eodhistoricaldata.com/api";
+/// Conservative default parallelism for `get_eod_batch`, chosen to stay well
+/// under EODHD's per-account rate limit.
+const DEFAULT_BATCH_CONCURRENCY: usize = 4;
+
+/// A single end-of-day OHLCV bar.
+#[pyclass]
+#[derive(Debug, Clone, Deserialize)]
+pub struct EodBar {
+    #[pyo3(get)]
+    pub date: String,
+    #[pyo3(get)]
+    pub open: f64,
+    #[pyo3(get)]
+    pub high: f64,
+    #[pyo3(get)]
+    pub low: f64,
+    #[pyo3(get)]
+    pub close: f64,
+    #[pyo3(get)]
+    pub adjusted_close: f64,
+    #[pyo3(get)]
+    pub volume: u64,
+}
+
+/// A single intraday OHLCV bar.
+#[pyclass]
+#[derive(Debug, Clone, Deserialize)]
+pub struct IntradayBar {
+    #[pyo3(get)]
+    pub datetime: String,
+    #[pyo3(get)]
+    pub gmtoffset: i64,
+    #[pyo3(get)]
+    pub open: f64,
+    #[pyo3(get)]
+    pub high: f64,
+    #[pyo3(get)]
+    pub low: f64,
+    #[pyo3(get)]
+    pub close: f64,
+    #[pyo3(get)]
+    pub volume: u64,
+}
+
+/// Sentiment scores EODHD attaches to each news item.
+#[derive(Debug, Clone, Default, Deserialize)]
+struct NewsSentiment {
+    #[serde(default)]
+    polarity: f64,
+    #[serde(default)]
+    neg: f64,
+    #[serde(default)]
+    neu: f64,
+    #[serde(default)]
+    pos: f64,
+}
+
+/// A single news item with its attached sentiment scores.
+#[pyclass]
+#[derive(Debug, Clone)]
+pub struct NewsItem {
+    #[pyo3(get)]
+    pub date: String,
+    #[pyo3(get)]
+    pub title: String,
+    #[pyo3(get)]
+    pub content: String,
+    #[pyo3(get)]
+    pub link: String,
+    #[pyo3(get)]
+    pub symbols: Vec<String>,
+    #[pyo3(get)]
+    pub sentiment_polarity: f64,
+    #[pyo3(get)]
+    pub sentiment_neg: f64,
+    #[pyo3(get)]
+    pub sentiment_neu: f64,
+    #[pyo3(get)]
+    pub sentiment_pos: f64,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawNewsItem {
+    date: String,
+    title: String,
+    #[serde(default)]
+    content: String,
+    #[serde(default)]
+    link: String,
+    #[serde(default)]
+    symbols: Vec<String>,
+    #[serde(default)]
+    sentiment: NewsSentiment,
+}
+
+impl From<RawNewsItem> for NewsItem {
+    fn from(raw: RawNewsItem) -> Self {
+        NewsItem {
+            date: raw.date,
+            title: raw.title,
+            content: raw.content,
+            link: raw.link,
+            symbols: raw.symbols,
+            sentiment_polarity: raw.sentiment.polarity,
+            sentiment_neg: raw.sentiment.neg,
+            sentiment_neu: raw.sentiment.neu,
+            sentiment_pos: raw.sentiment.pos,
+        }
+    }
+}
+
+/// Issues `url` via `client` and returns the decoded JSON body. Validates
+/// the status and content-type first so a non-200 or non-JSON response
+/// (EODHD returns plain-text error bodies) surfaces as a clear Python
+/// exception instead of a confusing deserialize failure.
+async fn get_json(client: &reqwest::Client, url: &str) -> PyResult<serde_json::Value> {
+    let resp = client
+        .get(url)
+        .send()
+        .await
+        .map_err(|e| PyRuntimeError::new_err(format!("EODHD request failed: {e}")))?;
+
+    let status = resp.status();
+    let is_json = resp
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|ct| ct.contains("json"));
+
+    if !status.is_success() || !is_json {
+        let body = resp.text().await.unwrap_or_default();
+        return Err(PyRuntimeError::new_err(format!(
+            "EODHD request failed ({status}): {body}"
+        )));
+    }
+
+    resp.json::<serde_json::Value>()
+        .await
+        .map_err(|e| PyRuntimeError::new_err(format!("failed to parse EODHD response: {e}")))
+}
+
+/// Fetches end-of-day OHLCV bars for `symbol` between `from` and `to`.
+#[pyfunction]
+pub fn get_eod(
+    py: Python<'_>,
+    symbol: String,
+    from: String,
+    to: String,
+    period: String,
+    api_token: String,
+) -> PyResult<Vec<EodBar>> {
+    py.allow_threads(|| {
+        let rt = tokio::runtime::Runtime::new()
+            .map_err(|e| PyRuntimeError::new_err(format!("failed to start runtime: {e}")))?;
+        rt.block_on(async move {
+            let client = reqwest::Client::new();
+            let url = format!(
+                "{BASE_URL}/eod/{symbol}?from={from}&to={to}&period={period}&api_token={api_token}&fmt=json"
+            );
+            let body = get_json(&client, &url).await?;
+            serde_json::from_value(body)
+                .map_err(|e| PyRuntimeError::new_err(format!("unexpected EOD response shape: {e}")))
+        })
+    })
+}
+
+/// Fetches intraday OHLCV bars for `symbol` between `from` and `to` at the
+/// given `interval` (e.g. "5m", "1h").
+#[pyfunction]
+pub fn get_intraday(
+    py: Python<'_>,
+    symbol: String,
+    interval: String,
+    from: String,
+    to: String,
+    api_token: String,
+) -> PyResult<Vec<IntradayBar>> {
+    py.allow_threads(|| {
+        let rt = tokio::runtime::Runtime::new()
+            .map_err(|e| PyRuntimeError::new_err(format!("failed to start runtime: {e}")))?;
+        rt.block_on(async move {
+            let client = reqwest::Client::new();
+            let url = format!(
+                "{BASE_URL}/intraday/{symbol}?interval={interval}&from={from}&to={to}&api_token={api_token}&fmt=json"
+            );
+            let body = get_json(&client, &url).await?;
+            serde_json::from_value(body).map_err(|e| {
+                PyRuntimeError::new_err(format!("unexpected intraday response shape: {e}"))
+            })
+        })
+    })
+}
+
+/// Fetches news items (with sentiment) for `symbol` between `from` and `to`.
+#[pyfunction]
+pub fn get_news(
+    py: Python<'_>,
+    symbol: String,
+    from: String,
+    to: String,
+    api_token: String,
+) -> PyResult<Vec<NewsItem>> {
+    py.allow_threads(|| {
+        let rt = tokio::runtime::Runtime::new()
+            .map_err(|e| PyRuntimeError::new_err(format!("failed to start runtime: {e}")))?;
+        rt.block_on(async move {
+            let client = reqwest::Client::new();
+            let url = format!(
+                "{BASE_URL}/news?s={symbol}&from={from}&to={to}&api_token={api_token}&fmt=json"
+            );
+            let body = get_json(&client, &url).await?;
+            let raw: Vec<RawNewsItem> = serde_json::from_value(body).map_err(|e| {
+                PyRuntimeError::new_err(format!("unexpected news response shape: {e}"))
+            })?;
+            Ok(raw.into_iter().map(NewsItem::from).collect())
+        })
+    })
+}
+
+/// Rejects a zero concurrency, which would make `buffer_unordered` never
+/// poll the underlying stream and hang `get_eod_batch` forever.
+fn validate_concurrency(concurrency: usize) -> Result<(), &'static str> {
+    if concurrency == 0 {
+        Err("concurrency must be at least 1")
+    } else {
+        Ok(())
+    }
+}
+
+/// Fetches end-of-day bars for many symbols at once, driving up to
+/// `concurrency` requests concurrently over a shared client. Returns a dict
+/// keyed by symbol; a symbol whose request fails gets its error message as
+/// the value instead of aborting the whole batch.
+#[pyfunction]
+#[pyo3(signature = (symbols, from, to, period, api_token, concurrency=DEFAULT_BATCH_CONCURRENCY))]
+pub fn get_eod_batch<'py>(
+    py: Python<'py>,
+    symbols: Vec<String>,
+    from: String,
+    to: String,
+    period: String,
+    api_token: String,
+    concurrency: usize,
+) -> PyResult<&'py PyDict> {
+    validate_concurrency(concurrency).map_err(PyValueError::new_err)?;
+
+    let results: Vec<(String, Result<Vec<EodBar>, String>)> = py.allow_threads(|| {
+        let rt = tokio::runtime::Runtime::new()
+            .map_err(|e| PyRuntimeError::new_err(format!("failed to start runtime: {e}")))?;
+        Ok::<_, PyErr>(rt.block_on(async move {
+            let client = reqwest::Client::new();
+            stream::iter(symbols.into_iter().map(|symbol| {
+                let client = client.clone();
+                let from = from.clone();
+                let to = to.clone();
+                let period = period.clone();
+                let api_token = api_token.clone();
+                async move {
+                    let url = format!(
+                        "{BASE_URL}/eod/{symbol}?from={from}&to={to}&period={period}&api_token={api_token}&fmt=json"
+                    );
+                    let outcome = match get_json(&client, &url).await {
+                        Ok(body) => serde_json::from_value::<Vec<EodBar>>(body)
+                            .map_err(|e| format!("unexpected EOD response shape: {e}")),
+                        Err(e) => Err(e.to_string()),
+                    };
+                    (symbol, outcome)
+                }
+            }))
+            .buffer_unordered(concurrency)
+            .collect::<Vec<_>>()
+            .await
+        }))
+    })?;
+
+    let dict = PyDict::new(py);
+    for (symbol, outcome) in results {
+        match outcome {
+            Ok(bars) => dict.set_item(symbol, bars.into_py(py))?,
+            Err(err) => dict.set_item(symbol, err)?,
+        }
+    }
+    Ok(dict)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_zero_concurrency() {
+        assert!(validate_concurrency(0).is_err());
+    }
+
+    #[test]
+    fn accepts_nonzero_concurrency() {
+        assert!(validate_concurrency(1).is_ok());
+        assert!(validate_concurrency(DEFAULT_BATCH_CONCURRENCY).is_ok());
+    }
+}